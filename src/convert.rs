@@ -1,9 +1,65 @@
 use bitcoin::hashes::hex::FromHex;
-use bitcoin::{Address, BlockHash, Txid};
+use bitcoin::{Address, BlockHash, ScriptBuf, Txid};
 use lightning_block_sync::http::JsonResponse;
+use serde_json::Value;
 use std::convert::TryInto;
 use std::str::FromStr;
 
+/// Looks up `field` in `val` as a JSON object and returns it as a string, or a descriptive
+/// `InvalidData` error naming the offending field if it's missing or of the wrong type.
+fn get_str<'a>(val: &'a Value, field: &str) -> std::io::Result<&'a str> {
+	val[field].as_str().ok_or_else(|| {
+		std::io::Error::new(
+			std::io::ErrorKind::InvalidData,
+			format!("Malformed JSON response: missing or non-string \"{}\" field", field),
+		)
+	})
+}
+
+/// Looks up `field` in `val` as a JSON object and returns it as a `u64`, or a descriptive
+/// `InvalidData` error naming the offending field if it's missing or of the wrong type.
+fn get_u64(val: &Value, field: &str) -> std::io::Result<u64> {
+	val[field].as_u64().ok_or_else(|| {
+		std::io::Error::new(
+			std::io::ErrorKind::InvalidData,
+			format!("Malformed JSON response: missing or non-numeric \"{}\" field", field),
+		)
+	})
+}
+
+/// Looks up `field` in `val` as a JSON object and returns it as an `i64`, or a descriptive
+/// `InvalidData` error naming the offending field if it's missing or of the wrong type.
+fn get_i64(val: &Value, field: &str) -> std::io::Result<i64> {
+	val[field].as_i64().ok_or_else(|| {
+		std::io::Error::new(
+			std::io::ErrorKind::InvalidData,
+			format!("Malformed JSON response: missing or non-numeric \"{}\" field", field),
+		)
+	})
+}
+
+/// Looks up `field` in `val` as a JSON object and returns it as an `f64`, or a descriptive
+/// `InvalidData` error naming the offending field if it's missing or of the wrong type.
+fn get_f64(val: &Value, field: &str) -> std::io::Result<f64> {
+	val[field].as_f64().ok_or_else(|| {
+		std::io::Error::new(
+			std::io::ErrorKind::InvalidData,
+			format!("Malformed JSON response: missing or non-numeric \"{}\" field", field),
+		)
+	})
+}
+
+/// Looks up `field` in `val` as a JSON object and returns it as a `bool`, or a descriptive
+/// `InvalidData` error naming the offending field if it's missing or of the wrong type.
+fn get_bool(val: &Value, field: &str) -> std::io::Result<bool> {
+	val[field].as_bool().ok_or_else(|| {
+		std::io::Error::new(
+			std::io::ErrorKind::InvalidData,
+			format!("Malformed JSON response: missing or non-boolean \"{}\" field", field),
+		)
+	})
+}
+
 pub struct FundedTx {
 	pub changepos: i64,
 	pub hex: String,
@@ -13,8 +69,64 @@ impl TryInto<FundedTx> for JsonResponse {
 	type Error = std::io::Error;
 	fn try_into(self) -> std::io::Result<FundedTx> {
 		Ok(FundedTx {
-			changepos: self.0["changepos"].as_i64().unwrap(),
-			hex: self.0["hex"].as_str().unwrap().to_string(),
+			changepos: get_i64(&self.0, "changepos")?,
+			hex: get_str(&self.0, "hex")?.to_string(),
+		})
+	}
+}
+
+pub struct WalletCreateFundedPsbt {
+	pub psbt: String,
+	pub fee: u64,
+	pub changepos: i64,
+}
+
+impl TryInto<WalletCreateFundedPsbt> for JsonResponse {
+	type Error = std::io::Error;
+	fn try_into(self) -> std::io::Result<WalletCreateFundedPsbt> {
+		Ok(WalletCreateFundedPsbt {
+			psbt: get_str(&self.0, "psbt")?.to_string(),
+			fee: bitcoin::Amount::from_btc(get_f64(&self.0, "fee")?)
+				.map_err(|_| {
+					std::io::Error::new(
+						std::io::ErrorKind::InvalidData,
+						"Malformed JSON response: invalid \"fee\" field",
+					)
+				})?
+				.to_sat(),
+			changepos: get_i64(&self.0, "changepos")?,
+		})
+	}
+}
+
+pub struct WalletProcessPsbt {
+	pub psbt: String,
+	pub complete: bool,
+}
+
+impl TryInto<WalletProcessPsbt> for JsonResponse {
+	type Error = std::io::Error;
+	fn try_into(self) -> std::io::Result<WalletProcessPsbt> {
+		Ok(WalletProcessPsbt {
+			psbt: get_str(&self.0, "psbt")?.to_string(),
+			complete: get_bool(&self.0, "complete")?,
+		})
+	}
+}
+
+pub struct FinalizePsbt {
+	pub psbt: Option<String>,
+	pub hex: Option<String>,
+	pub complete: bool,
+}
+
+impl TryInto<FinalizePsbt> for JsonResponse {
+	type Error = std::io::Error;
+	fn try_into(self) -> std::io::Result<FinalizePsbt> {
+		Ok(FinalizePsbt {
+			psbt: self.0["psbt"].as_str().map(|s| s.to_string()),
+			hex: self.0["hex"].as_str().map(|s| s.to_string()),
+			complete: get_bool(&self.0, "complete")?,
 		})
 	}
 }
@@ -24,7 +136,17 @@ pub struct RawTx(pub String);
 impl TryInto<RawTx> for JsonResponse {
 	type Error = std::io::Error;
 	fn try_into(self) -> std::io::Result<RawTx> {
-		Ok(RawTx(self.0.as_str().unwrap().to_string()))
+		Ok(RawTx(
+			self.0
+				.as_str()
+				.ok_or_else(|| {
+					std::io::Error::new(
+						std::io::ErrorKind::InvalidData,
+						"Malformed JSON response: expected a raw transaction string",
+					)
+				})?
+				.to_string(),
+		))
 	}
 }
 
@@ -37,8 +159,8 @@ impl TryInto<SignedTx> for JsonResponse {
 	type Error = std::io::Error;
 	fn try_into(self) -> std::io::Result<SignedTx> {
 		Ok(SignedTx {
-			hex: self.0["hex"].as_str().unwrap().to_string(),
-			complete: self.0["complete"].as_bool().unwrap(),
+			hex: get_str(&self.0, "hex")?.to_string(),
+			complete: get_bool(&self.0, "complete")?,
 		})
 	}
 }
@@ -47,7 +169,17 @@ pub struct NewAddress(pub String);
 impl TryInto<NewAddress> for JsonResponse {
 	type Error = std::io::Error;
 	fn try_into(self) -> std::io::Result<NewAddress> {
-		Ok(NewAddress(self.0.as_str().unwrap().to_string()))
+		Ok(NewAddress(
+			self.0
+				.as_str()
+				.ok_or_else(|| {
+					std::io::Error::new(
+						std::io::ErrorKind::InvalidData,
+						"Malformed JSON response: expected an address string",
+					)
+				})?
+				.to_string(),
+		))
 	}
 }
 
@@ -84,7 +216,10 @@ impl TryInto<MempoolMinFeeResponse> for JsonResponse {
 	type Error = std::io::Error;
 	fn try_into(self) -> std::io::Result<MempoolMinFeeResponse> {
 		let errored = !self.0["errors"].is_null();
-		assert_eq!(self.0["maxmempool"].as_u64(), Some(300000000));
+		// Bitcoin Core's default maxmempool is 300MB; if a node has been configured with a
+		// different value we still want the feerate below, so this is surfaced as a soft error
+		// via `errored` rather than a hard panic.
+		let errored = errored || self.0["maxmempool"].as_u64() != Some(300000000);
 		Ok(MempoolMinFeeResponse {
 			errored,
 			feerate_sat_per_kw: match self.0["mempoolminfee"].as_f64() {
@@ -110,10 +245,15 @@ impl TryInto<BlockchainInfo> for JsonResponse {
 	type Error = std::io::Error;
 	fn try_into(self) -> std::io::Result<BlockchainInfo> {
 		Ok(BlockchainInfo {
-			latest_height: self.0["blocks"].as_u64().unwrap() as usize,
-			latest_blockhash: BlockHash::from_hex(self.0["bestblockhash"].as_str().unwrap())
-				.unwrap(),
-			chain: self.0["chain"].as_str().unwrap().to_string(),
+			latest_height: get_u64(&self.0, "blocks")? as usize,
+			latest_blockhash: BlockHash::from_hex(get_str(&self.0, "bestblockhash")?)
+				.map_err(|_| {
+					std::io::Error::new(
+						std::io::ErrorKind::InvalidData,
+						"Malformed JSON response: invalid \"bestblockhash\" field",
+					)
+				})?,
+			chain: get_str(&self.0, "chain")?.to_string(),
 		})
 	}
 }
@@ -122,7 +262,12 @@ pub struct ListUnspentUtxo {
 	pub txid: Txid,
 	pub vout: u32,
 	pub amount: u64,
-	pub address: Address,
+	pub script_pubkey: ScriptBuf,
+	pub address: Option<Address>,
+	pub desc: Option<String>,
+	pub confirmations: u32,
+	pub spendable: bool,
+	pub solvable: bool,
 }
 
 pub struct ListUnspentResponse(pub Vec<ListUnspentUtxo>);
@@ -133,16 +278,53 @@ impl TryInto<ListUnspentResponse> for JsonResponse {
 		let utxos = self
 			.0
 			.as_array()
-			.unwrap()
+			.ok_or_else(|| {
+				std::io::Error::new(
+					std::io::ErrorKind::InvalidData,
+					"Malformed JSON response: expected a list of UTXOs",
+				)
+			})?
 			.iter()
-			.map(|utxo| ListUnspentUtxo {
-				txid: Txid::from_str(&utxo["txid"].as_str().unwrap().to_string()).unwrap(),
-				vout: utxo["vout"].as_u64().unwrap() as u32,
-				amount: bitcoin::Amount::from_btc(utxo["amount"].as_f64().unwrap())
-					.unwrap()
-					.to_sat(),
-				address: Address::from_str(&utxo["address"].as_str().unwrap().to_string()).unwrap(),
+			.map(|utxo| {
+				// Descriptor and watch-only wallets commonly omit `address`, surfacing only
+				// `scriptPubKey` (and often `desc`), so `address` is parsed on a best-effort basis.
+				let address = utxo["address"]
+					.as_str()
+					.and_then(|addr| Address::from_str(addr).ok());
+				Ok(ListUnspentUtxo {
+					txid: Txid::from_str(get_str(utxo, "txid")?).map_err(|_| {
+						std::io::Error::new(
+							std::io::ErrorKind::InvalidData,
+							"Malformed JSON response: invalid \"txid\" field",
+						)
+					})?,
+					vout: get_u64(utxo, "vout")? as u32,
+					amount: bitcoin::Amount::from_btc(get_f64(utxo, "amount")?)
+						.map_err(|_| {
+							std::io::Error::new(
+								std::io::ErrorKind::InvalidData,
+								"Malformed JSON response: invalid \"amount\" field",
+							)
+						})?
+						.to_sat(),
+					script_pubkey: ScriptBuf::from(
+						Vec::<u8>::from_hex(get_str(utxo, "scriptPubKey")?).map_err(|_| {
+							std::io::Error::new(
+								std::io::ErrorKind::InvalidData,
+								"Malformed JSON response: invalid \"scriptPubKey\" field",
+							)
+						})?,
+					),
+					address,
+					desc: utxo["desc"].as_str().map(|s| s.to_string()),
+					confirmations: get_u64(utxo, "confirmations")? as u32,
+					spendable: get_bool(utxo, "spendable")?,
+					solvable: get_bool(utxo, "solvable")?,
+				})
 			})
+			.collect::<std::io::Result<Vec<ListUnspentUtxo>>>()?
+			.into_iter()
+			.filter(|utxo| utxo.spendable && utxo.solvable)
 			.collect();
 		Ok(ListUnspentResponse(utxos))
 	}